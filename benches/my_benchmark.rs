@@ -60,7 +60,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     group.bench_function(name, |b| {
         b.iter(|| {
             let mut interpreter = Interpreter::new(&mut io);
-            interpreter.interpret(&contents);
+            interpreter.interpret(&contents).unwrap();
         })
     });
     group.finish();
@@ -78,7 +78,7 @@ pub fn criterion_benchmark_al(c: &mut Criterion) {
     group.bench_function(name, |b| {
         b.iter(|| {
             let mut interpreter = Interpreter::new(&mut io);
-            interpreter.interpret(&contents);
+            interpreter.interpret(&contents).unwrap();
         })
     });
     group.finish();
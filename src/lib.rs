@@ -38,11 +38,131 @@ pub mod io {
 
     impl StdIn for RawIO {
         fn read(&mut self) -> Result<char, std::io::Error> {
-            std::io::stdin()
-                .bytes()
-                .next()
-                .unwrap()
-                .map(|byte| byte as char)
+            match std::io::stdin().bytes().next() {
+                Some(result) => result.map(|byte| byte as char),
+                None => Err(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "stdin reached EOF",
+                )),
+            }
+        }
+    }
+}
+
+pub mod error {
+    use std::fmt;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BrainfuckError {
+        /// A `]` was encountered with no matching `[` on the loop stack.
+        UnmatchedLoopEnd { pos: usize },
+        /// Compilation finished with a `[` still on the loop stack.
+        UnmatchedLoopStart { pos: usize },
+        /// The data pointer moved outside the tape while in `PointerMode::Halt`.
+        PointerOutOfBounds { pos: isize },
+        /// `VmConfig::cells` was zero, so the tape has no cell for the pointer to address.
+        EmptyTape,
+    }
+
+    impl fmt::Display for BrainfuckError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                BrainfuckError::UnmatchedLoopEnd { pos } => {
+                    write!(f, "unmatched ']' at position {}", pos)
+                }
+                BrainfuckError::UnmatchedLoopStart { pos } => {
+                    write!(f, "unmatched '[' at position {}", pos)
+                }
+                BrainfuckError::PointerOutOfBounds { pos } => {
+                    write!(f, "data pointer moved out of bounds (to {})", pos)
+                }
+                BrainfuckError::EmptyTape => {
+                    write!(f, "tape must have at least one cell")
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for BrainfuckError {}
+}
+
+pub mod config {
+    /// How the data pointer behaves when `<`/`>` (or a scan loop) would move it off the tape.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum PointerMode {
+        /// Wrap around to the other end of the tape. This is the classic, and default, behavior.
+        #[default]
+        Wrap,
+        /// Report a `BrainfuckError::PointerOutOfBounds` instead of moving off the tape.
+        Halt,
+        /// Grow the tape to the right on demand; moving past cell 0 is still an error.
+        Grow,
+    }
+
+    /// What the `,` command stores in the current cell when the input stream is at EOF.
+    /// Brainfuck dialects disagree on this, so it's configurable rather than picking one.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum EofBehavior {
+        /// Leave the cell untouched. This is the classic, and default, behavior.
+        #[default]
+        Unchanged,
+        /// Set the cell to 0.
+        Zero,
+        /// Set the cell to -1, i.e. the maximum value for the configured cell width.
+        NegativeOne,
+    }
+
+    /// The integer width backing each tape cell, and therefore the modulus `+`/`-` wrap at.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum CellWidth {
+        #[default]
+        U8,
+        U16,
+        U32,
+    }
+
+    impl CellWidth {
+        /// The bitmask a cell value is kept within, e.g. `0xFF` for `U8`.
+        pub fn mask(&self) -> u32 {
+            match self {
+                CellWidth::U8 => u8::MAX as u32,
+                CellWidth::U16 => u16::MAX as u32,
+                CellWidth::U32 => u32::MAX,
+            }
+        }
+    }
+
+    /// How a cell's value is written to output by `.`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum OutputMode {
+        /// Output the low byte of the cell, as the original byte-cell interpreter did.
+        #[default]
+        Byte,
+        /// Output the cell as a Unicode scalar value, falling back to `Byte` behavior for
+        /// cell values that aren't a valid `char` (e.g. surrogate code points).
+        Utf8,
+    }
+
+    /// Configuration for a [`crate::vm::VirtualMachine`] or [`crate::Interpreter`]: tape size,
+    /// out-of-bounds pointer behavior, EOF behavior for `,`, cell width, and output mode.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct VmConfig {
+        pub cells: usize,
+        pub pointer_mode: PointerMode,
+        pub eof_behavior: EofBehavior,
+        pub cell_width: CellWidth,
+        pub output_mode: OutputMode,
+    }
+
+    impl Default for VmConfig {
+        fn default() -> Self {
+            Self {
+                cells: 3000,
+                pointer_mode: PointerMode::default(),
+                eof_behavior: EofBehavior::default(),
+                cell_width: CellWidth::default(),
+                output_mode: OutputMode::default(),
+            }
         }
     }
 }
@@ -65,14 +185,25 @@ mod token {
 mod bytecode {
     #[derive(Debug, Clone, PartialEq, Eq)]
     pub enum Bytecode {
-        IncrementPointer,
-        DecrementPointer,
-        IncrementValue,
-        DecrementValue,
+        IncrementPointer { amount: usize },
+        DecrementPointer { amount: usize },
+        IncrementValue { amount: usize },
+        DecrementValue { amount: usize },
         OutputValue,
         InputValue,
         LoopStart { jump_to: usize },
         LoopEnd { jump_to: usize },
+        /// Set the current cell to zero in O(1); rewritten by the optimizer from the
+        /// `[-]`/`[+]` idiom.
+        SetZero,
+        /// Move the pointer by `step` cells at a time until the current cell is zero;
+        /// rewritten by the optimizer from the `[>]`/`[<]` idiom.
+        ScanRight { step: usize },
+        ScanLeft { step: usize },
+        /// Add `factor * current cell` to the cell at `offset` from the pointer, wrapping.
+        /// Rewritten by the optimizer from copy/multiply loops like `[-<+>]`; always
+        /// followed by a `SetZero` for the cell the loop counted down.
+        MultiplyAdd { offset: isize, factor: i64 },
     }
 }
 
@@ -122,6 +253,7 @@ mod scanner {
 mod compiler {
 
     use super::bytecode::Bytecode;
+    use crate::error::BrainfuckError;
     use crate::token::Token;
 
     pub struct Compiler {
@@ -135,40 +267,45 @@ mod compiler {
             }
         }
 
-        pub fn compile_bytecode(&mut self, input: Vec<Token>) -> Vec<Bytecode> {
+        pub fn compile_bytecode(
+            &mut self,
+            input: Vec<Token>,
+        ) -> Result<Vec<Bytecode>, BrainfuckError> {
+            self.loop_stack.clear();
             let mut output = Vec::new();
 
-            input
-                .into_iter()
-                .filter(|t| t != &Token::Space)
-                .enumerate()
-                .for_each(|(i, t)| {
-                    let next_bytecode = match t {
-                        Token::GreaterThan => Bytecode::IncrementPointer,
-                        Token::LessThan => Bytecode::DecrementPointer,
-                        Token::Plus => Bytecode::IncrementValue,
-                        Token::Minus => Bytecode::DecrementValue,
-                        Token::Dot => Bytecode::OutputValue,
-                        Token::Comma => Bytecode::InputValue,
-                        Token::LeftSquareBracket => {
-                            self.loop_stack.push(i);
-                            Bytecode::LoopStart { jump_to: 0 }
-                        }
-                        Token::RightSquareBracket => {
-                            let loop_start = self.loop_stack.pop().unwrap();
-                            // TODO: judge
-                            *output.get_mut(loop_start).unwrap() =
-                                Bytecode::LoopStart { jump_to: i };
-                            Bytecode::LoopEnd {
-                                jump_to: loop_start,
-                            }
+            for (i, t) in input.into_iter().filter(|t| t != &Token::Space).enumerate() {
+                let next_bytecode = match t {
+                    Token::GreaterThan => Bytecode::IncrementPointer { amount: 1 },
+                    Token::LessThan => Bytecode::DecrementPointer { amount: 1 },
+                    Token::Plus => Bytecode::IncrementValue { amount: 1 },
+                    Token::Minus => Bytecode::DecrementValue { amount: 1 },
+                    Token::Dot => Bytecode::OutputValue,
+                    Token::Comma => Bytecode::InputValue,
+                    Token::LeftSquareBracket => {
+                        self.loop_stack.push(i);
+                        Bytecode::LoopStart { jump_to: 0 }
+                    }
+                    Token::RightSquareBracket => {
+                        let loop_start = self
+                            .loop_stack
+                            .pop()
+                            .ok_or(BrainfuckError::UnmatchedLoopEnd { pos: i })?;
+                        *output.get_mut(loop_start).unwrap() = Bytecode::LoopStart { jump_to: i };
+                        Bytecode::LoopEnd {
+                            jump_to: loop_start,
                         }
-                        Token::Space => panic!("no space"),
-                    };
-                    output.push(next_bytecode);
-                });
+                    }
+                    Token::Space => panic!("no space"),
+                };
+                output.push(next_bytecode);
+            }
 
-            output
+            if let Some(&pos) = self.loop_stack.first() {
+                return Err(BrainfuckError::UnmatchedLoopStart { pos });
+            }
+
+            Ok(output)
         }
     }
 
@@ -177,42 +314,114 @@ mod compiler {
         let mut compiler = Compiler::new();
 
         let input = vec![Token::GreaterThan, Token::LessThan, Token::Plus];
-        let output = compiler.compile_bytecode(input);
+        let output = compiler.compile_bytecode(input).unwrap();
         assert_eq!(
             output,
             vec![
-                Bytecode::IncrementPointer,
-                Bytecode::DecrementPointer,
-                Bytecode::IncrementValue
+                Bytecode::IncrementPointer { amount: 1 },
+                Bytecode::DecrementPointer { amount: 1 },
+                Bytecode::IncrementValue { amount: 1 }
             ]
         )
     }
+
+    #[test]
+    fn unmatched_loop_end() {
+        let mut compiler = Compiler::new();
+
+        let input = vec![Token::RightSquareBracket];
+        assert_eq!(
+            compiler.compile_bytecode(input),
+            Err(BrainfuckError::UnmatchedLoopEnd { pos: 0 })
+        );
+    }
+
+    #[test]
+    fn unmatched_loop_start() {
+        let mut compiler = Compiler::new();
+
+        let input = vec![Token::LeftSquareBracket];
+        assert_eq!(
+            compiler.compile_bytecode(input),
+            Err(BrainfuckError::UnmatchedLoopStart { pos: 0 })
+        );
+    }
 }
 
 mod vm {
     use crate::{
         bytecode::Bytecode,
+        config::{CellWidth, EofBehavior, OutputMode, PointerMode, VmConfig},
+        error::BrainfuckError,
         io::{StdIn, StdInOut, StdOut},
     };
 
     pub struct VirtualMachine<'a> {
-        memory: Vec<u8>,
+        memory: Vec<u32>,
         mem_pointer: usize,
         code_pointer: usize,
+        pointer_mode: PointerMode,
+        eof_behavior: EofBehavior,
+        cell_width: CellWidth,
+        output_mode: OutputMode,
         pub io: &'a mut dyn StdInOut,
     }
 
     impl<'a> VirtualMachine<'a> {
-        pub fn new(io: &'a mut dyn StdInOut) -> Self {
-            Self {
-                memory: vec![0; 3000],
+        pub fn with_config(
+            io: &'a mut dyn StdInOut,
+            config: VmConfig,
+        ) -> Result<Self, BrainfuckError> {
+            if config.cells == 0 {
+                return Err(BrainfuckError::EmptyTape);
+            }
+
+            Ok(Self {
+                memory: vec![0; config.cells],
                 mem_pointer: 0,
                 code_pointer: 0,
+                pointer_mode: config.pointer_mode,
+                eof_behavior: config.eof_behavior,
+                cell_width: config.cell_width,
+                output_mode: config.output_mode,
                 io,
+            })
+        }
+
+        /// Move the data pointer by `delta` cells, honoring `pointer_mode`.
+        fn move_pointer(&mut self, delta: isize) -> Result<(), BrainfuckError> {
+            self.mem_pointer = self.resolve_offset(delta)?;
+            Ok(())
+        }
+
+        /// Resolve `self.mem_pointer + offset` to a tape index, honoring `pointer_mode`
+        /// (growing the tape in `PointerMode::Grow`), without moving the pointer.
+        fn resolve_offset(&mut self, offset: isize) -> Result<usize, BrainfuckError> {
+            let target = self.mem_pointer as isize + offset;
+
+            match self.pointer_mode {
+                PointerMode::Wrap => Ok(target.rem_euclid(self.memory.len() as isize) as usize),
+                PointerMode::Halt => {
+                    if target < 0 || target as usize >= self.memory.len() {
+                        Err(BrainfuckError::PointerOutOfBounds { pos: target })
+                    } else {
+                        Ok(target as usize)
+                    }
+                }
+                PointerMode::Grow => {
+                    if target < 0 {
+                        return Err(BrainfuckError::PointerOutOfBounds { pos: target });
+                    }
+                    let target = target as usize;
+                    if target >= self.memory.len() {
+                        self.memory.resize(target + 1, 0);
+                    }
+                    Ok(target)
+                }
             }
         }
 
-        pub fn run(&mut self, bytecodes: Vec<Bytecode>) {
+        pub fn run(&mut self, bytecodes: Vec<Bytecode>) -> Result<(), BrainfuckError> {
             loop {
                 if let Some(bytecode) = bytecodes.get(self.code_pointer) {
                     // println!(
@@ -220,36 +429,45 @@ mod vm {
                     //     bytecode, self.mem_pointer, self.memory[self.mem_pointer]
                     // );
                     match bytecode {
-                        Bytecode::IncrementPointer => {
-                            if self.mem_pointer == self.memory.len() - 1 {
-                                self.mem_pointer = 0;
-                            } else {
-                                self.mem_pointer += 1;
-                            }
+                        Bytecode::IncrementPointer { amount } => {
+                            self.move_pointer(*amount as isize)?;
                         }
-                        Bytecode::DecrementPointer => {
-                            if self.mem_pointer == 0 {
-                                self.mem_pointer = self.memory.len() - 1;
-                            } else {
-                                self.mem_pointer -= 1;
-                            }
+                        Bytecode::DecrementPointer { amount } => {
+                            self.move_pointer(-(*amount as isize))?;
                         }
-                        Bytecode::IncrementValue => {
+                        Bytecode::IncrementValue { amount } => {
+                            let mask = self.cell_width.mask();
                             self.memory[self.mem_pointer] =
-                                self.memory[self.mem_pointer].overflowing_add(1).0;
+                                self.memory[self.mem_pointer].wrapping_add(*amount as u32) & mask;
                         }
-                        Bytecode::DecrementValue => {
+                        Bytecode::DecrementValue { amount } => {
+                            let mask = self.cell_width.mask();
                             self.memory[self.mem_pointer] =
-                                self.memory[self.mem_pointer].overflowing_sub(1).0;
+                                self.memory[self.mem_pointer].wrapping_sub(*amount as u32) & mask;
                         }
                         Bytecode::OutputValue => {
-                            self.io.print(self.memory[self.mem_pointer] as char)
+                            let value = self.memory[self.mem_pointer];
+                            let c = match self.output_mode {
+                                OutputMode::Byte => value as u8 as char,
+                                OutputMode::Utf8 => {
+                                    char::from_u32(value).unwrap_or(value as u8 as char)
+                                }
+                            };
+                            self.io.print(c)
                         }
-                        Bytecode::InputValue => {
-                            if let Ok(c) = self.io.read() {
-                                self.memory[self.mem_pointer] = c as u8;
+                        Bytecode::InputValue => match self.io.read() {
+                            Ok(c) => self.memory[self.mem_pointer] = c as u32 & self.cell_width.mask(),
+                            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                                match self.eof_behavior {
+                                    EofBehavior::Unchanged => {}
+                                    EofBehavior::Zero => self.memory[self.mem_pointer] = 0,
+                                    EofBehavior::NegativeOne => {
+                                        self.memory[self.mem_pointer] = self.cell_width.mask()
+                                    }
+                                }
                             }
-                        }
+                            Err(_) => {}
+                        },
                         Bytecode::LoopStart { jump_to } => {
                             if self.memory[self.mem_pointer] == 0 {
                                 self.code_pointer = *jump_to;
@@ -260,6 +478,33 @@ mod vm {
                                 self.code_pointer = *jump_to;
                             }
                         }
+                        Bytecode::SetZero => {
+                            self.memory[self.mem_pointer] = 0;
+                        }
+                        Bytecode::ScanRight { step } => {
+                            while self.memory[self.mem_pointer] != 0 {
+                                self.move_pointer(*step as isize)?;
+                            }
+                        }
+                        Bytecode::ScanLeft { step } => {
+                            while self.memory[self.mem_pointer] != 0 {
+                                self.move_pointer(-(*step as isize))?;
+                            }
+                        }
+                        Bytecode::MultiplyAdd { offset, factor } => {
+                            let source = self.memory[self.mem_pointer] as i64;
+                            if source == 0 {
+                                // The original loop would not have run, so the pointer never
+                                // reaches `offset`.
+                                self.code_pointer += 1;
+                                continue;
+                            }
+                            let target = self.resolve_offset(*offset)?;
+                            let modulus = self.cell_width.mask() as i64 + 1;
+                            let delta = source.wrapping_mul(*factor);
+                            self.memory[target] =
+                                (self.memory[target] as i64 + delta).rem_euclid(modulus) as u32;
+                        }
                     }
                 } else {
                     // EOF
@@ -268,17 +513,393 @@ mod vm {
 
                 self.code_pointer += 1;
             }
+
+            Ok(())
         }
 
         pub fn print_memory(&self) {
             println!("{:?}", self.memory);
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::{compiler::Compiler, scanner::scan, testing::TestStdOut};
+
+        fn compile(source: &str) -> Vec<Bytecode> {
+            Compiler::new().compile_bytecode(scan(source)).unwrap()
+        }
+
+        #[test]
+        fn with_config_rejects_an_empty_tape() {
+            let mut io = TestStdOut::new();
+            let config = VmConfig {
+                cells: 0,
+                ..VmConfig::default()
+            };
+            assert_eq!(
+                VirtualMachine::with_config(&mut io, config).err(),
+                Some(BrainfuckError::EmptyTape)
+            );
+        }
+
+        #[test]
+        fn halt_mode_errors_on_out_of_bounds() {
+            let mut io = TestStdOut::new();
+            let config = VmConfig {
+                cells: 2,
+                pointer_mode: PointerMode::Halt,
+                ..VmConfig::default()
+            };
+            let mut vm = VirtualMachine::with_config(&mut io, config).unwrap();
+            assert_eq!(
+                vm.run(compile(">>")),
+                Err(BrainfuckError::PointerOutOfBounds { pos: 2 })
+            );
+        }
+
+        #[test]
+        fn grow_mode_extends_tape_to_the_right() {
+            let mut io = TestStdOut::new();
+            let config = VmConfig {
+                cells: 1,
+                pointer_mode: PointerMode::Grow,
+                ..VmConfig::default()
+            };
+            let mut vm = VirtualMachine::with_config(&mut io, config).unwrap();
+            vm.run(compile(">>>+")).unwrap();
+            assert_eq!(vm.memory, vec![0, 0, 0, 1]);
+        }
+
+        #[test]
+        fn eof_behavior_is_applied_once_input_is_exhausted() {
+            let cases = [
+                (EofBehavior::Unchanged, 42),
+                (EofBehavior::Zero, 0),
+                (EofBehavior::NegativeOne, 255),
+            ];
+
+            for (eof_behavior, expected) in cases {
+                let mut io = TestStdOut::new();
+                let config = VmConfig {
+                    eof_behavior,
+                    ..VmConfig::default()
+                };
+                let mut vm = VirtualMachine::with_config(&mut io, config).unwrap();
+                vm.memory[0] = 42;
+                vm.run(compile(",")).unwrap();
+                assert_eq!(vm.memory[0], expected);
+            }
+        }
+
+        #[test]
+        fn cell_width_controls_value_wraparound() {
+            let mut io = TestStdOut::new();
+            let config = VmConfig {
+                cell_width: CellWidth::U16,
+                ..VmConfig::default()
+            };
+            let mut vm = VirtualMachine::with_config(&mut io, config).unwrap();
+            vm.memory[0] = u16::MAX as u32;
+            vm.run(compile("+")).unwrap();
+            assert_eq!(vm.memory[0], 0);
+        }
+
+        #[test]
+        fn utf8_output_mode_prints_unicode_scalar_values() {
+            let mut io = TestStdOut::new();
+            let config = VmConfig {
+                cell_width: CellWidth::U32,
+                output_mode: OutputMode::Utf8,
+                ..VmConfig::default()
+            };
+            {
+                let mut vm = VirtualMachine::with_config(&mut io, config).unwrap();
+                vm.memory[0] = '日' as u32;
+                vm.run(compile(".")).unwrap();
+            }
+            assert_eq!(io.output, vec!['日']);
+        }
+    }
+}
+
+mod optimizer {
+    use crate::bytecode::Bytecode;
+
+    /// Optimize a stream of raw, one-instruction-per-token bytecode: fold consecutive
+    /// pointer/value instructions into a single instruction carrying a run length, then
+    /// rewrite common loop idioms into dedicated O(1)/O(n) bytecodes. Raw bytecode from
+    /// `Compiler::compile_bytecode` is left untouched so it stays available for debugging.
+    pub fn optimize(bytecodes: Vec<Bytecode>) -> Vec<Bytecode> {
+        let bytecodes = coalesce_runs(bytecodes);
+        recognize_idioms(bytecodes)
+    }
+
+    fn coalesce_runs(bytecodes: Vec<Bytecode>) -> Vec<Bytecode> {
+        let mut old_to_new = vec![0; bytecodes.len()];
+        let mut output = Vec::new();
+        let mut i = 0;
+
+        while i < bytecodes.len() {
+            let run_start = i;
+            let merged = match &bytecodes[i] {
+                Bytecode::IncrementPointer { .. } => {
+                    let mut amount = 0;
+                    while let Some(Bytecode::IncrementPointer { amount: a }) = bytecodes.get(i) {
+                        amount += a;
+                        old_to_new[i] = output.len();
+                        i += 1;
+                    }
+                    Some(Bytecode::IncrementPointer { amount })
+                }
+                Bytecode::DecrementPointer { .. } => {
+                    let mut amount = 0;
+                    while let Some(Bytecode::DecrementPointer { amount: a }) = bytecodes.get(i) {
+                        amount += a;
+                        old_to_new[i] = output.len();
+                        i += 1;
+                    }
+                    Some(Bytecode::DecrementPointer { amount })
+                }
+                Bytecode::IncrementValue { .. } => {
+                    let mut amount = 0;
+                    while let Some(Bytecode::IncrementValue { amount: a }) = bytecodes.get(i) {
+                        amount += a;
+                        old_to_new[i] = output.len();
+                        i += 1;
+                    }
+                    Some(Bytecode::IncrementValue { amount })
+                }
+                Bytecode::DecrementValue { .. } => {
+                    let mut amount = 0;
+                    while let Some(Bytecode::DecrementValue { amount: a }) = bytecodes.get(i) {
+                        amount += a;
+                        old_to_new[i] = output.len();
+                        i += 1;
+                    }
+                    Some(Bytecode::DecrementValue { amount })
+                }
+                _ => None,
+            };
+
+            match merged {
+                Some(merged) => output.push(merged),
+                None => {
+                    old_to_new[run_start] = output.len();
+                    output.push(bytecodes[run_start].clone());
+                    i += 1;
+                }
+            }
+        }
+
+        remap_jumps(&mut output, &old_to_new);
+        output
+    }
+
+    fn recognize_idioms(bytecodes: Vec<Bytecode>) -> Vec<Bytecode> {
+        let mut old_to_new = vec![0; bytecodes.len()];
+        let mut output = Vec::new();
+        let mut i = 0;
+
+        while i < bytecodes.len() {
+            if let Bytecode::LoopStart { jump_to: end } = bytecodes[i] {
+                let body = &bytecodes[i + 1..end];
+                let replacement = recognize_clear_loop(body)
+                    .or_else(|| recognize_scan_loop(body))
+                    .or_else(|| recognize_multiply_loop(body));
+
+                if let Some(replacement) = replacement {
+                    old_to_new[i..=end].fill(output.len());
+                    output.extend(replacement);
+                    i = end + 1;
+                    continue;
+                }
+            }
+
+            old_to_new[i] = output.len();
+            output.push(bytecodes[i].clone());
+            i += 1;
+        }
+
+        remap_jumps(&mut output, &old_to_new);
+        output
+    }
+
+    /// `[-]`/`[+]`: a loop whose body is a single decrement/increment of the current cell
+    /// clears it in O(1) instead of looping down to zero.
+    fn recognize_clear_loop(body: &[Bytecode]) -> Option<Vec<Bytecode>> {
+        match body {
+            [Bytecode::DecrementValue { amount: 1 }] | [Bytecode::IncrementValue { amount: 1 }] => {
+                Some(vec![Bytecode::SetZero])
+            }
+            _ => None,
+        }
+    }
+
+    /// `[>]`/`[<]`: a loop whose body is a single pointer move scans for the next zero cell.
+    fn recognize_scan_loop(body: &[Bytecode]) -> Option<Vec<Bytecode>> {
+        match body {
+            [Bytecode::IncrementPointer { amount }] => {
+                Some(vec![Bytecode::ScanRight { step: *amount }])
+            }
+            [Bytecode::DecrementPointer { amount }] => {
+                Some(vec![Bytecode::ScanLeft { step: *amount }])
+            }
+            _ => None,
+        }
+    }
+
+    /// `[-<+>]`-style copy/multiply loops: the body nets the pointer back to its start,
+    /// decrements the current cell by exactly one per iteration, and adds some multiple of
+    /// it to one or more other cells. Rewritten into `MultiplyAdd`s plus a trailing `SetZero`.
+    fn recognize_multiply_loop(body: &[Bytecode]) -> Option<Vec<Bytecode>> {
+        let mut offset: isize = 0;
+        let mut deltas: Vec<(isize, i64)> = Vec::new();
+
+        for bytecode in body {
+            match bytecode {
+                Bytecode::IncrementPointer { amount } => offset += *amount as isize,
+                Bytecode::DecrementPointer { amount } => offset -= *amount as isize,
+                Bytecode::IncrementValue { amount } => add_delta(&mut deltas, offset, *amount as i64),
+                Bytecode::DecrementValue { amount } => {
+                    add_delta(&mut deltas, offset, -(*amount as i64))
+                }
+                _ => return None,
+            }
+        }
+
+        if offset != 0 {
+            return None;
+        }
+
+        let current_delta = deltas
+            .iter()
+            .find(|(o, _)| *o == 0)
+            .map(|(_, d)| *d)
+            .unwrap_or(0);
+        if current_delta != -1 {
+            return None;
+        }
+
+        let mut replacement: Vec<Bytecode> = deltas
+            .into_iter()
+            .filter(|(offset, factor)| *offset != 0 && *factor != 0)
+            .map(|(offset, factor)| Bytecode::MultiplyAdd { offset, factor })
+            .collect();
+        replacement.push(Bytecode::SetZero);
+        Some(replacement)
+    }
+
+    fn add_delta(deltas: &mut Vec<(isize, i64)>, offset: isize, delta: i64) {
+        match deltas.iter_mut().find(|(o, _)| *o == offset) {
+            Some(entry) => entry.1 += delta,
+            None => deltas.push((offset, delta)),
+        }
+    }
+
+    fn remap_jumps(bytecodes: &mut [Bytecode], old_to_new: &[usize]) {
+        for bytecode in bytecodes.iter_mut() {
+            match bytecode {
+                Bytecode::LoopStart { jump_to } | Bytecode::LoopEnd { jump_to } => {
+                    *jump_to = old_to_new[*jump_to];
+                }
+                _ => {}
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::{
+            compiler::Compiler, scanner::scan, testing::TestStdOut, vm::VirtualMachine, VmConfig,
+        };
+
+        fn compile(source: &str) -> Vec<Bytecode> {
+            Compiler::new().compile_bytecode(scan(source)).unwrap()
+        }
+
+        #[test]
+        fn coalesces_runs() {
+            assert_eq!(
+                optimize(compile("++++")),
+                vec![Bytecode::IncrementValue { amount: 4 }]
+            );
+            assert_eq!(
+                optimize(compile(">>><<")),
+                vec![
+                    Bytecode::IncrementPointer { amount: 3 },
+                    Bytecode::DecrementPointer { amount: 2 },
+                ]
+            );
+        }
+
+        #[test]
+        fn recognizes_clear_loop() {
+            assert_eq!(optimize(compile("[-]")), vec![Bytecode::SetZero]);
+            assert_eq!(optimize(compile("[+]")), vec![Bytecode::SetZero]);
+        }
+
+        #[test]
+        fn recognizes_scan_loop() {
+            assert_eq!(
+                optimize(compile("[>]")),
+                vec![Bytecode::ScanRight { step: 1 }]
+            );
+            assert_eq!(
+                optimize(compile("[<<]")),
+                vec![Bytecode::ScanLeft { step: 2 }]
+            );
+        }
+
+        #[test]
+        fn recognizes_multiply_loop() {
+            assert_eq!(
+                optimize(compile("[->+<]")),
+                vec![
+                    Bytecode::MultiplyAdd { offset: 1, factor: 1 },
+                    Bytecode::SetZero,
+                ]
+            );
+        }
+
+        #[test]
+        fn optimized_output_matches_unoptimized() {
+            let programs = [
+                "++++++++ [>++++++++++++>+++++++++++++<<-] >++++. -. >+++++++. <+. +.",
+                "+[>[<->+[>+++>[+++++++++++>][]-[<]>-]]++++++++++<]>>>>>>----.<<+++.<-.",
+                "[->+>+<<]++++>>.",
+            ];
+
+            for source in programs {
+                let raw = compile(source);
+                let optimized = optimize(raw.clone());
+
+                let mut raw_io = TestStdOut::new();
+                VirtualMachine::with_config(&mut raw_io, VmConfig::default())
+                    .unwrap()
+                    .run(raw)
+                    .unwrap();
+
+                let mut optimized_io = TestStdOut::new();
+                VirtualMachine::with_config(&mut optimized_io, VmConfig::default())
+                    .unwrap()
+                    .run(optimized)
+                    .unwrap();
+
+                assert_eq!(raw_io.output, optimized_io.output);
+            }
+        }
+    }
 }
 
 /// # Brainfuck Interpreter
 pub mod interpret {
-    use crate::{compiler::Compiler, io::StdInOut, scanner::scan, vm::VirtualMachine};
+    use crate::{
+        compiler::Compiler, config::VmConfig, error::BrainfuckError, io::StdInOut, optimizer,
+        scanner::scan, vm::VirtualMachine,
+    };
 
     pub struct Interpreter<'a> {
         vm: VirtualMachine<'a>,
@@ -287,27 +908,37 @@ pub mod interpret {
 
     impl<'a> Interpreter<'a> {
         pub fn new(io: &'a mut dyn StdInOut) -> Self {
-            Self {
-                vm: VirtualMachine::new(io),
+            Self::with_config(io, VmConfig::default())
+                .expect("VmConfig::default() always configures a non-empty tape")
+        }
+
+        pub fn with_config(
+            io: &'a mut dyn StdInOut,
+            config: VmConfig,
+        ) -> Result<Self, BrainfuckError> {
+            Ok(Self {
+                vm: VirtualMachine::with_config(io, config)?,
                 compiler: Compiler::new(),
-            }
+            })
         }
 
-        pub fn interpret(&mut self, source_code: &str) {
+        pub fn interpret(&mut self, source_code: &str) -> Result<(), BrainfuckError> {
             let tokens = scan(source_code);
-            let bytecodes = self.compiler.compile_bytecode(tokens);
-            self.vm.run(bytecodes);
+            let bytecodes = self.compiler.compile_bytecode(tokens)?;
+            self.vm.run(optimizer::optimize(bytecodes))
         }
     }
 }
 
+pub use config::{CellWidth, EofBehavior, OutputMode, PointerMode, VmConfig};
+pub use error::BrainfuckError;
 pub use interpret::Interpreter;
 pub use io::RawIO;
 
 pub(crate) mod testing {
     use std::{collections::VecDeque, fs::File, io::Read};
 
-    use crate::{compiler, io, scanner::scan, vm};
+    use crate::{compiler, io, scanner::scan, vm, VmConfig};
 
     #[macro_export]
     macro_rules! gen_tests {
@@ -332,9 +963,9 @@ pub(crate) mod testing {
         }
 
         let tokens = scan(source_code);
-        let res = compiler::Compiler::new().compile_bytecode(tokens);
-        let mut vm = vm::VirtualMachine::new(&mut io_buffer);
-        vm.run(res);
+        let res = compiler::Compiler::new().compile_bytecode(tokens).unwrap();
+        let mut vm = vm::VirtualMachine::with_config(&mut io_buffer, VmConfig::default()).unwrap();
+        vm.run(res).unwrap();
 
         if let Some(output) = output {
             assert_eq!(io_buffer.output, output.chars().collect::<Vec<_>>());
@@ -394,9 +1025,10 @@ pub(crate) mod testing {
 
     impl io::StdIn for TestStdOut {
         fn read(&mut self) -> Result<char, std::io::Error> {
-            self.input
-                .pop_front()
-                .ok_or(std::io::Error::new(std::io::ErrorKind::Other, "No input"))
+            self.input.pop_front().ok_or(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "no input left",
+            ))
         }
     }
 }
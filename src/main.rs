@@ -1,5 +1,6 @@
 use std::io::{Read, Write};
 
+use brainfuck_rs::{CellWidth, EofBehavior, OutputMode, PointerMode, VmConfig};
 use clap::{Arg, Command};
 
 fn main() {
@@ -12,16 +13,101 @@ fn main() {
                 .help("Sets the input file to use")
                 .index(1),
         )
+        .arg(
+            Arg::new("cells")
+                .long("cells")
+                .help("Sets the number of cells on the tape")
+                .takes_value(true)
+                .validator(|s| s.parse::<usize>().map(|_| ()).map_err(|e| e.to_string()))
+                .default_value("3000"),
+        )
+        .arg(
+            Arg::new("pointer-mode")
+                .long("pointer-mode")
+                .help("Sets the behavior of `<`/`>` when the pointer runs off the tape")
+                .takes_value(true)
+                .possible_values(["wrap", "halt", "grow"])
+                .default_value("wrap"),
+        )
+        .arg(
+            Arg::new("eof-behavior")
+                .long("eof-behavior")
+                .help("Sets what `,` stores in the current cell once input is exhausted")
+                .takes_value(true)
+                .possible_values(["unchanged", "zero", "negative-one"])
+                .default_value("unchanged"),
+        )
+        .arg(
+            Arg::new("cell-width")
+                .long("cell-width")
+                .help("Sets the integer width of each tape cell")
+                .takes_value(true)
+                .possible_values(["8", "16", "32"])
+                .default_value("8"),
+        )
+        .arg(
+            Arg::new("output-mode")
+                .long("output-mode")
+                .help("Sets how `.` writes a cell's value to output")
+                .takes_value(true)
+                .possible_values(["byte", "utf8"])
+                .default_value("byte"),
+        )
         .get_matches();
 
+    let cells = matches
+        .value_of("cells")
+        .unwrap()
+        .parse()
+        .expect("clap validates cells is a valid usize");
+    let pointer_mode = match matches.value_of("pointer-mode").unwrap() {
+        "wrap" => PointerMode::Wrap,
+        "halt" => PointerMode::Halt,
+        "grow" => PointerMode::Grow,
+        _ => unreachable!("clap validates possible_values"),
+    };
+    let eof_behavior = match matches.value_of("eof-behavior").unwrap() {
+        "unchanged" => EofBehavior::Unchanged,
+        "zero" => EofBehavior::Zero,
+        "negative-one" => EofBehavior::NegativeOne,
+        _ => unreachable!("clap validates possible_values"),
+    };
+    let cell_width = match matches.value_of("cell-width").unwrap() {
+        "8" => CellWidth::U8,
+        "16" => CellWidth::U16,
+        "32" => CellWidth::U32,
+        _ => unreachable!("clap validates possible_values"),
+    };
+    let output_mode = match matches.value_of("output-mode").unwrap() {
+        "byte" => OutputMode::Byte,
+        "utf8" => OutputMode::Utf8,
+        _ => unreachable!("clap validates possible_values"),
+    };
+    let config = VmConfig {
+        cells,
+        pointer_mode,
+        eof_behavior,
+        cell_width,
+        output_mode,
+    };
+
     let mut rawio = brainfuck_rs::io::RawIO::new();
-    let mut interpreter = brainfuck_rs::Interpreter::new(&mut rawio);
+    let mut interpreter = match brainfuck_rs::Interpreter::with_config(&mut rawio, config) {
+        Ok(interpreter) => interpreter,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    };
     if let Some(input_file) = matches.value_of("INPUT") {
         // Interpreter mode.
         let mut input_file = std::fs::File::open(input_file).unwrap();
         let mut content = String::new();
         input_file.read_to_string(&mut content).unwrap();
-        interpreter.interpret(&content);
+        if let Err(e) = interpreter.interpret(&content) {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
     } else {
         // REPL mode.
         loop {
@@ -29,7 +115,9 @@ fn main() {
             std::io::stdout().flush().unwrap();
             let mut input = String::new();
             std::io::stdin().read_line(&mut input).unwrap();
-            interpreter.interpret(&input);
+            if let Err(e) = interpreter.interpret(&input) {
+                eprintln!("error: {}", e);
+            }
         }
     }
 }